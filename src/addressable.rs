@@ -0,0 +1,74 @@
+use crate::Error;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Random-access memory view over an absolute target address space, the way an
+/// emulator's memory bus exposes the regions it maps.
+pub trait Addressable {
+    /// Read `count` bytes starting at the absolute address `addr`.
+    ///
+    /// Returns [`Error::AddressNotMapped`] if the requested range is not fully
+    /// contained within a single mapped region (e.g. it falls in a gap).
+    fn read(&self, addr: u32, count: usize) -> Result<Vec<u8>, Error>;
+
+    /// Overwrite `bytes.len()` bytes starting at the absolute address `addr`.
+    ///
+    /// Returns [`Error::AddressNotMapped`] if the requested range is not fully
+    /// contained within a single mapped region.
+    fn write(&mut self, addr: u32, bytes: &[u8]) -> Result<(), Error>;
+
+    /// Read a single byte at `addr`.
+    fn read_u8(&self, addr: u32) -> Result<u8, Error> {
+        Ok(self.read(addr, 1)?[0])
+    }
+
+    /// Read a big-endian `u16` at `addr`.
+    fn read_beu16(&self, addr: u32) -> Result<u16, Error> {
+        let bytes = self.read(addr, 2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Read a big-endian `u32` at `addr`.
+    fn read_beu32(&self, addr: u32) -> Result<u32, Error> {
+        let bytes = self.read(addr, 4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read a little-endian `u16` at `addr`.
+    fn read_leu16(&self, addr: u32) -> Result<u16, Error> {
+        let bytes = self.read(addr, 2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Read a little-endian `u32` at `addr`.
+    fn read_leu32(&self, addr: u32) -> Result<u32, Error> {
+        let bytes = self.read(addr, 4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Write a single byte at `addr`.
+    fn write_u8(&mut self, addr: u32, value: u8) -> Result<(), Error> {
+        self.write(addr, &[value])
+    }
+
+    /// Write a big-endian `u16` at `addr`.
+    fn write_beu16(&mut self, addr: u32, value: u16) -> Result<(), Error> {
+        self.write(addr, &value.to_be_bytes())
+    }
+
+    /// Write a big-endian `u32` at `addr`.
+    fn write_beu32(&mut self, addr: u32, value: u32) -> Result<(), Error> {
+        self.write(addr, &value.to_be_bytes())
+    }
+
+    /// Write a little-endian `u16` at `addr`.
+    fn write_leu16(&mut self, addr: u32, value: u16) -> Result<(), Error> {
+        self.write(addr, &value.to_le_bytes())
+    }
+
+    /// Write a little-endian `u32` at `addr`.
+    fn write_leu32(&mut self, addr: u32, value: u32) -> Result<(), Error> {
+        self.write(addr, &value.to_le_bytes())
+    }
+}
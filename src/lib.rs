@@ -1,10 +1,33 @@
 //! SREC file parsing and memory layout utilities.
+//!
+//! Without the `std` feature this crate is `no_std` + `alloc`: [`SRecord::from_lines`]
+//! and [`SRecord::from_bytes`] work from a line iterator or a raw byte slice, which is
+//! enough to parse S-records received over a link with no filesystem (e.g. inside a
+//! bootloader). The `std` feature additionally provides [`SRecord::from_srec`] (reads a
+//! [`File`]) and [`SRecord::write`] (writes to anything implementing [`std::io::Write`]).
 
-use std::{fs::File, io::BufRead, io::BufReader};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::{fs::File, io::BufRead, io::BufReader, io::Write};
+
+#[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::{string::ToString, vec::Vec};
+
+mod addressable;
 mod record;
-pub use record::{Address, Data, Record};
+mod segment;
+pub use addressable::Addressable;
+pub use record::{Address, Address32, Data, Record};
+pub use segment::Segment;
 
 /// Errors which may occur during reading or parsing SREC files.
+///
+/// Requires `thiserror` 2.x or newer so the derive works under `no_std` (it implements
+/// against `core::error::Error` instead of unconditionally requiring `std::error::Error`).
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Checksum did not match calculated checksum")]
@@ -15,6 +38,10 @@ pub enum Error {
     UnexpectedCharacter,
     #[error("Can't open srec file")]
     SrecFileError,
+    #[error("Address is not mapped by any data region")]
+    AddressNotMapped,
+    #[error("Record was truncated before its declared fields were complete")]
+    UnexpectedEof,
 }
 
 /// Represents a parsed SREC file and its memory layout.
@@ -27,6 +54,29 @@ pub struct SRecord<const MAX: u32> {
     data: Vec<u8>,
     /// Total data length in bytes.
     data_length: usize,
+    /// Each S1/S2/S3 record's data as its own segment (for [`SRecord::build`], each
+    /// input region as one segment), preserving gaps between them.
+    segments: Vec<Segment>,
+    /// `(begin, end, record index)` for each S1/S2/S3 record's `[begin, end)` data
+    /// range, sorted by `begin` for binary-search containment lookups.
+    ranges: Vec<(Address32, Address32, usize)>,
+    /// Addresses where two records wrote conflicting byte values, detected once
+    /// while `ranges` was built.
+    conflicts: Vec<Conflict>,
+}
+
+/// Two records both wrote `address`, but with different byte values.
+///
+/// This usually indicates a bad linker script or a merge bug, and is otherwise
+/// silently hidden by [`SRecord`] concatenating data in file order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    /// The conflicting address.
+    pub address: Address32,
+    /// The byte value from the earlier record.
+    pub expected: u8,
+    /// The byte value from the later record.
+    pub found: u8,
 }
 
 impl<const MAX: u32> SRecord<MAX> {
@@ -55,42 +105,113 @@ impl<const MAX: u32> SRecord<MAX> {
         self.data_length
     }
 
-    /// Parse an SREC file and build the memory layout and data.
+    /// Returns the individual, non-merged data segments in file order (for
+    /// [`SRecord::build`], in the order of its input regions).
+    pub fn get_segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Parse SREC records from a line iterator and build the memory layout and data.
     ///
     /// - Merges adjacent/overlapping regions up to MAX bytes per region.
     /// - Supports S1, S2, S3 records for data.
-    pub fn from_srec(f: File) -> Result<Self, Error> {
-        let reader = BufReader::new(f);
+    ///
+    /// This is the `no_std`-friendly core that [`SRecord::from_bytes`] and the `std`-only
+    /// [`SRecord::from_srec`] both build on, so it has no filesystem dependency.
+    pub fn from_lines<'a, I: Iterator<Item = &'a str>>(lines: I) -> Result<Self, Error> {
         let mut records = Vec::new();
         let mut regions = Vec::new();
         let mut data = Vec::new();
+        let mut segments = Vec::new();
+        let mut ranges = Vec::new();
 
         // Parse each line and collect regions and data
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                let rec = record::Record::parse_from_str(&line)?;
-                match &rec {
-                    record::Record::S1(d) => {
-                        regions.push((d.address as u32, d.data.len()));
-                        data.extend(&d.data);
-                    }
-                    record::Record::S2(d) => {
-                        regions.push((d.address, d.data.len()));
-                        data.extend(&d.data);
-                    }
-                    record::Record::S3(d) => {
-                        regions.push((d.address, d.data.len()));
-                        data.extend(&d.data);
-                    }
-                    _ => {}
+        for line in lines {
+            let rec = record::Record::parse_from_str(line)?;
+            match &rec {
+                record::Record::S1(d) => {
+                    regions.push((d.address as u32, d.data.len()));
+                    data.extend(&d.data);
+                    ranges.push((
+                        d.address as u32,
+                        (d.address as u32).saturating_add(d.data.len() as u32),
+                        records.len(),
+                    ));
+                    segments.push(Segment {
+                        base: Address::Address16(d.address),
+                        contents: d.data.clone(),
+                    });
                 }
-                records.push(rec);
-            } else {
-                return Err(Error::SrecFileError);
+                record::Record::S2(d) => {
+                    regions.push((d.address, d.data.len()));
+                    data.extend(&d.data);
+                    ranges.push((
+                        d.address,
+                        d.address.saturating_add(d.data.len() as u32),
+                        records.len(),
+                    ));
+                    segments.push(Segment {
+                        base: Address::Address24(d.address),
+                        contents: d.data.clone(),
+                    });
+                }
+                record::Record::S3(d) => {
+                    regions.push((d.address, d.data.len()));
+                    data.extend(&d.data);
+                    ranges.push((
+                        d.address,
+                        d.address.saturating_add(d.data.len() as u32),
+                        records.len(),
+                    ));
+                    segments.push(Segment {
+                        base: Address::Address32(d.address),
+                        contents: d.data.clone(),
+                    });
+                }
+                _ => {}
             }
+            records.push(rec);
         }
 
-        // Sort regions by address and merge adjacent/overlapping ones, splitting if MAX is exceeded
+        ranges.sort_by_key(|&(begin, _, _)| begin);
+        let conflicts = Self::find_conflicts(&segments);
+        let final_regions = Self::merge_regions(regions);
+        let data_length = data.len();
+
+        Ok(Self {
+            record: records,
+            data_memory_layout: final_regions,
+            data,
+            data_length,
+            segments,
+            ranges,
+            conflicts,
+        })
+    }
+
+    /// Parse SREC records from a raw byte slice, e.g. one received over a serial link
+    /// with no filesystem. Available without the `std` feature.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let text = core::str::from_utf8(bytes).map_err(|_| Error::UnexpectedCharacter)?;
+        Self::from_lines(text.lines())
+    }
+
+    /// Parse an SREC file and build the memory layout and data.
+    ///
+    /// Thin `std`-only wrapper around [`SRecord::from_lines`].
+    #[cfg(feature = "std")]
+    pub fn from_srec(f: File) -> Result<Self, Error> {
+        let reader = BufReader::new(f);
+        let lines = reader
+            .lines()
+            .collect::<std::io::Result<Vec<String>>>()
+            .map_err(|_| Error::SrecFileError)?;
+        Self::from_lines(lines.iter().map(|line| line.as_str()))
+    }
+
+    /// Sort regions by address and merge adjacent/overlapping ones, splitting any
+    /// region (or merge result) that would exceed `MAX` bytes.
+    fn merge_regions(mut regions: Vec<(u32, usize)>) -> Vec<(Address, usize)> {
         regions.sort_by_key(|&(addr, _)| addr);
         let mut merged = Vec::new();
         for (addr, size) in regions {
@@ -128,24 +249,236 @@ impl<const MAX: u32> SRecord<MAX> {
                 final_regions.push((Address::Address32(addr), size));
             }
         }
+        final_regions
+    }
+
+    /// Compare every pair of segments and report addresses where they disagree on the
+    /// byte value of an address they both cover.
+    fn find_conflicts(segments: &[Segment]) -> Vec<Conflict> {
+        let mut conflicts = Vec::new();
+        for i in 0..segments.len() {
+            for j in (i + 1)..segments.len() {
+                let a = &segments[i];
+                let b = &segments[j];
+                let a_base = a.base.as_u32();
+                let b_base = b.base.as_u32();
+                let overlap_start = a_base.max(b_base);
+                let overlap_end = a_base
+                    .saturating_add(a.contents.len() as u32)
+                    .min(b_base.saturating_add(b.contents.len() as u32));
+                for address in overlap_start..overlap_end {
+                    let expected = a.contents[(address - a_base) as usize];
+                    let found = b.contents[(address - b_base) as usize];
+                    if expected != found {
+                        conflicts.push(Conflict {
+                            address,
+                            expected,
+                            found,
+                        });
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Returns every detected conflict: an address that two records both wrote with
+    /// different byte values. Empty for a well-formed file.
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// Returns the data record whose `[begin, end)` range contains `addr`, the way a
+    /// DWARF address-range table maps an address to its owning compilation unit.
+    ///
+    /// Ranges may overlap (this type tracks conflicting regions rather than rejecting
+    /// them), so containment isn't guaranteed to live in the immediate predecessor of
+    /// `addr` in `begin` order — every candidate with `begin <= addr` is checked.
+    pub fn record_at(&self, addr: Address32) -> Option<&Record> {
+        let idx = self.ranges.partition_point(|&(begin, _, _)| begin <= addr);
+        self.ranges[..idx]
+            .iter()
+            .rev()
+            .find(|&&(begin, end, _)| addr >= begin && addr < end)
+            .map(|&(_, _, record_index)| &self.record[record_index])
+    }
+
+    /// Returns every data record whose range overlaps `[begin, end)`.
+    pub fn records_in_range(&self, begin: Address32, end: Address32) -> Vec<&Record> {
+        self.ranges
+            .iter()
+            .filter(|&&(r_begin, r_end, _)| r_begin < end && r_end > begin)
+            .map(|&(_, _, record_index)| &self.record[record_index])
+            .collect()
+    }
+
+    /// Build an [`SRecord`] from scratch out of raw `(address, data)` regions, producing
+    /// a full set of records (optional S0 header, S1/S2/S3 data split into `chunk_len`-byte
+    /// pieces, an S5/S6 record count, and the matching S9/S8/S7 termination record) ready
+    /// to be handed to [`SRecord::write`].
+    ///
+    /// The data-record width (S1/S2/S3) is picked per region from its `Address` variant;
+    /// the termination record matches the widest data record actually emitted.
+    pub fn build(header: Option<&str>, chunk_len: usize, regions: &[(Address, Vec<u8>)]) -> Self {
+        let chunk_len = chunk_len.max(1);
+        let mut records = Vec::new();
+        if let Some(header) = header {
+            records.push(Record::S0(header.to_string()));
+        }
+
+        let mut layout_regions = Vec::new();
+        let mut data = Vec::new();
+        let mut segments = Vec::new();
+        let mut ranges = Vec::new();
+        let mut data_record_count: u32 = 0;
+        let mut widest: u8 = 1;
+
+        for (address, bytes) in regions {
+            // Clamp to the widest chunk that still fits the one-byte byte-count field
+            // for this address width, so `build` never emits a record `write` would
+            // happily serialize but `parse_from_str_strict` would reject.
+            let chunk_len = chunk_len.min(address.max_payload_len());
+            let base = address.as_u32();
+            layout_regions.push((base, bytes.len()));
+            data.extend(bytes);
+            segments.push(Segment {
+                base: address.clone(),
+                contents: bytes.clone(),
+            });
+
+            for (i, chunk) in bytes.chunks(chunk_len).enumerate() {
+                let chunk_addr = base.saturating_add((i * chunk_len) as u32);
+                ranges.push((
+                    chunk_addr,
+                    chunk_addr.saturating_add(chunk.len() as u32),
+                    records.len(),
+                ));
+                let rec = match address {
+                    Address::Address16(_) => Record::S1(Data {
+                        address: chunk_addr as u16,
+                        data: chunk.to_vec(),
+                    }),
+                    Address::Address24(_) => {
+                        widest = widest.max(2);
+                        Record::S2(Data {
+                            address: chunk_addr,
+                            data: chunk.to_vec(),
+                        })
+                    }
+                    Address::Address32(_) => {
+                        widest = widest.max(3);
+                        Record::S3(Data {
+                            address: chunk_addr,
+                            data: chunk.to_vec(),
+                        })
+                    }
+                };
+                records.push(rec);
+                data_record_count += 1;
+            }
+        }
+
+        if data_record_count <= u16::MAX as u32 {
+            records.push(Record::S5(data_record_count as u16));
+        } else {
+            records.push(Record::S6(data_record_count));
+        }
 
+        records.push(match widest {
+            1 => Record::S9(0),
+            2 => Record::S8(0),
+            _ => Record::S7(0),
+        });
+
+        ranges.sort_by_key(|&(begin, _, _)| begin);
+        let conflicts = Self::find_conflicts(&segments);
+        let data_memory_layout = Self::merge_regions(layout_regions);
         let data_length = data.len();
 
-        Ok(Self {
+        Self {
             record: records,
-            data_memory_layout: final_regions,
+            data_memory_layout,
             data,
             data_length,
-        })
+            segments,
+            ranges,
+            conflicts,
+        }
+    }
+
+    /// Flatten the segments into one contiguous image over `[start, end)`, padding any
+    /// gaps between segments (and before/after them) with `fill`. This is the standard
+    /// last step before writing a firmware image to a programmer.
+    pub fn to_binary(&self, fill: u8, start: Address, end: Address) -> Vec<u8> {
+        let start = start.as_u32();
+        let end = end.as_u32();
+        let mut image = alloc::vec![fill; end.saturating_sub(start) as usize];
+
+        for segment in &self.segments {
+            let seg_start = segment.base.as_u32();
+            let seg_end = seg_start + segment.contents.len() as u32;
+            let overlap_start = seg_start.max(start);
+            let overlap_end = seg_end.min(end);
+            if overlap_start < overlap_end {
+                let len = (overlap_end - overlap_start) as usize;
+                let src = (overlap_start - seg_start) as usize;
+                let dst = (overlap_start - start) as usize;
+                image[dst..dst + len].copy_from_slice(&segment.contents[src..src + len]);
+            }
+        }
+
+        image
+    }
+
+    /// Write every record back out as S-record text, one record per line.
+    #[cfg(feature = "std")]
+    pub fn write<W: Write>(&self, mut w: W) -> Result<(), Error> {
+        for rec in &self.record {
+            writeln!(w, "{}", rec).map_err(|_| Error::SrecFileError)?;
+        }
+        Ok(())
+    }
+
+    /// Locate the segment covering `count` bytes starting at the absolute address
+    /// `addr`, and the byte offset into that segment's own `contents`.
+    ///
+    /// This indexes into `segments` (each of which owns its bytes at its own base
+    /// address) rather than into the flat `data` buffer, so it gives correct results
+    /// regardless of the order records appeared in the source file — `data` is only
+    /// ever in file-insertion order, not address order.
+    fn locate(&self, addr: u32, count: usize) -> Result<(usize, usize), Error> {
+        for (seg_index, segment) in self.segments.iter().enumerate() {
+            let seg_start = segment.base.as_u32();
+            let seg_end = seg_start + segment.contents.len() as u32;
+            if addr >= seg_start && addr + count as u32 <= seg_end {
+                return Ok((seg_index, (addr - seg_start) as usize));
+            }
+        }
+        Err(Error::AddressNotMapped)
+    }
+}
+
+impl<const MAX: u32> Addressable for SRecord<MAX> {
+    fn read(&self, addr: u32, count: usize) -> Result<Vec<u8>, Error> {
+        let (seg_index, offset) = self.locate(addr, count)?;
+        Ok(self.segments[seg_index].contents[offset..offset + count].to_vec())
+    }
+
+    fn write(&mut self, addr: u32, bytes: &[u8]) -> Result<(), Error> {
+        let (seg_index, offset) = self.locate(addr, bytes.len())?;
+        self.segments[seg_index].contents[offset..offset + bytes.len()].copy_from_slice(bytes);
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "std")]
     use std::fs::File;
 
     /// Test loading an SREC file and checking the memory layout and data.
+    #[cfg(feature = "std")]
     #[test]
     fn test_from_srec_file() {
         let file = File::open("test_data/test.srec").expect("Failed to open test.srec");
@@ -168,4 +501,154 @@ mod tests {
             println!("Region: {:?}, size: {:#X}", addr, size);
         }
     }
+
+    /// Building a file from raw regions and writing it back out should produce
+    /// well-formed, parseable S-record text.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_build_and_write_round_trips() {
+        let regions = vec![
+            (Address::Address32(0x0000_0000), vec![0xDE, 0xAD, 0xBE, 0xEF]),
+            (Address::Address32(0x0000_1000), (0..20).collect::<Vec<u8>>()),
+        ];
+        let srec = SRecord::<0x8000>::build(Some("TEST"), 16, &regions);
+
+        let mut out = Vec::new();
+        srec.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        let lines: Vec<&str> = text.lines().collect();
+        // Header + 3 data records (4 bytes, 16 bytes, 4 bytes) + count + termination
+        assert_eq!(lines.len(), 6);
+        for line in &lines {
+            Record::parse_from_str(line).expect("re-parsing a built record should succeed");
+        }
+        assert!(lines.last().unwrap().starts_with("S7"));
+    }
+
+    /// A `chunk_len` wider than a record's byte-count field can express must be
+    /// clamped, not silently truncated into a corrupt record.
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_build_clamps_oversized_chunk_len() {
+        let regions = vec![(Address::Address32(0x1000), vec![0u8; 256])];
+        let srec = SRecord::<0x8000>::build(None, 300, &regions);
+
+        let mut out = Vec::new();
+        srec.write(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        for line in text.lines() {
+            Record::parse_from_str_strict(line)
+                .expect("build must never emit a record parse_from_str_strict rejects");
+        }
+    }
+
+    #[test]
+    fn test_addressable_read_write() {
+        let regions = vec![
+            (Address::Address32(0x1000), vec![0x01, 0x02, 0x03, 0x04]),
+            (Address::Address32(0x2000), vec![0xAA, 0xBB]),
+        ];
+        let mut srec = SRecord::<0x8000>::build(None, 16, &regions);
+
+        assert_eq!(srec.read_u8(0x1000).unwrap(), 0x01);
+        assert_eq!(srec.read_beu16(0x1002).unwrap(), 0x0304);
+        assert_eq!(srec.read_leu16(0x1002).unwrap(), 0x0403);
+
+        srec.write_u8(0x2000, 0xFF).unwrap();
+        assert_eq!(srec.read_u8(0x2000).unwrap(), 0xFF);
+
+        // A read spanning the gap between the two regions is not mapped.
+        assert!(matches!(
+            srec.read(0x1002, 4),
+            Err(Error::AddressNotMapped)
+        ));
+    }
+
+    /// Records in descending address order must still read back correctly: `data` is
+    /// only ever in file-insertion order, so `locate` must not assume it mirrors the
+    /// sorted `data_memory_layout`/segment order.
+    #[test]
+    fn test_addressable_read_independent_of_record_order() {
+        let high = Record::S3(Data {
+            address: 0x2000,
+            data: vec![0xAA, 0xBB],
+        });
+        let low = Record::S3(Data {
+            address: 0x1000,
+            data: vec![0x01, 0x02, 0x03, 0x04],
+        });
+        let text = format!("{}\n{}\n", high, low);
+        let srec = SRecord::<0x8000>::from_bytes(text.as_bytes()).unwrap();
+
+        assert_eq!(srec.read_u8(0x1000).unwrap(), 0x01);
+        assert_eq!(srec.read_u8(0x2000).unwrap(), 0xAA);
+
+        let out_of_order = [
+            (Address::Address32(0x2000), vec![0xAA, 0xBB]),
+            (Address::Address32(0x1000), vec![0x01, 0x02, 0x03, 0x04]),
+        ];
+        let built = SRecord::<0x8000>::build(None, 16, &out_of_order);
+        assert_eq!(built.read_u8(0x1000).unwrap(), 0x01);
+        assert_eq!(built.read_u8(0x2000).unwrap(), 0xAA);
+    }
+
+    #[test]
+    fn test_to_binary_fills_gaps() {
+        let regions = vec![
+            (Address::Address32(0x0000), vec![0x01, 0x02]),
+            (Address::Address32(0x0008), vec![0xAA, 0xBB]),
+        ];
+        let srec = SRecord::<0x8000>::build(None, 16, &regions);
+        assert_eq!(srec.get_segments().len(), 2);
+
+        let image = srec.to_binary(0xFF, Address::Address32(0x0000), Address::Address32(0x000A));
+        assert_eq!(
+            image,
+            vec![0x01, 0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xAA, 0xBB]
+        );
+    }
+
+    #[test]
+    fn test_record_at_and_records_in_range() {
+        let regions = vec![
+            (Address::Address32(0x1000), vec![0x01, 0x02, 0x03, 0x04]),
+            (Address::Address32(0x2000), vec![0xAA, 0xBB]),
+        ];
+        let srec = SRecord::<0x8000>::build(None, 16, &regions);
+
+        assert!(matches!(srec.record_at(0x1002), Some(Record::S3(d)) if d.address == 0x1000));
+        assert!(srec.record_at(0x1500).is_none());
+
+        let found = srec.records_in_range(0x1002, 0x2001);
+        assert_eq!(found.len(), 2);
+    }
+
+    /// `record_at` must find containment in a wider range even when a narrower,
+    /// later-starting range also overlaps `addr` but doesn't itself contain it.
+    #[test]
+    fn test_record_at_finds_containment_behind_overlapping_range() {
+        let regions = vec![
+            (Address::Address32(0x0000), vec![0x00, 0x01, 0x02, 0x03, 0x04]),
+            (Address::Address32(0x0003), vec![0xFF]),
+        ];
+        let srec = SRecord::<0x8000>::build(None, 16, &regions);
+
+        assert!(matches!(srec.record_at(4), Some(Record::S3(d)) if d.address == 0x0000));
+    }
+
+    #[test]
+    fn test_conflicts_detected_for_overlapping_data() {
+        let regions = vec![
+            (Address::Address32(0x1000), vec![0x01, 0x02, 0x03, 0x04]),
+            (Address::Address32(0x1002), vec![0xFF, 0xFF]),
+        ];
+        let srec = SRecord::<0x8000>::build(None, 16, &regions);
+
+        let conflicts = srec.conflicts();
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].address, 0x1002);
+        assert_eq!(conflicts[0].expected, 0x03);
+        assert_eq!(conflicts[0].found, 0xFF);
+    }
 }
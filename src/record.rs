@@ -1,5 +1,10 @@
+use core::fmt;
+
 use crate::Error;
 
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String, string::ToString, vec::Vec};
+
 pub type Address16 = u16;
 pub type Address24 = u32;
 pub type Address32 = u32;
@@ -26,6 +31,26 @@ pub enum Address {
 }
 
 impl Address {
+    /// Widen this address to a plain `u32`, regardless of its original width.
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            Address::Address16(addr) => addr as u32,
+            Address::Address24(addr) => addr,
+            Address::Address32(addr) => addr,
+        }
+    }
+
+    /// The largest data payload (in bytes) a single S1/S2/S3 record at this address
+    /// width can carry: the byte-count field is one byte, so `address_len + data_len +
+    /// 1 (checksum) <= 255`.
+    pub fn max_payload_len(&self) -> usize {
+        match self {
+            Address::Address16(_) => 252,
+            Address::Address24(_) => 251,
+            Address::Address32(_) => 250,
+        }
+    }
+
     pub fn to_le_bytes(self) -> Vec<u8> {
         match self {
             Address::Address16(addr) => addr.to_le_bytes().to_vec(),
@@ -68,6 +93,35 @@ pub enum Record {
 }
 
 impl Record {
+    /// Compute the S-record checksum: the ones-complement of the least-significant
+    /// byte of the sum of the byte-count, the address bytes, and the data bytes.
+    fn checksum(byte_count: u8, address: &[u8], data: &[u8]) -> u8 {
+        let sum: u32 = byte_count as u32
+            + address.iter().map(|&b| b as u32).sum::<u32>()
+            + data.iter().map(|&b| b as u32).sum::<u32>();
+        !(sum as u8)
+    }
+
+    /// Render a single record as `"S" + type digit + byte count + address + data + checksum`,
+    /// all as upper-case hex, given the already width-sized address bytes.
+    fn format(type_digit: u8, address: &[u8], data: &[u8]) -> String {
+        let byte_count = (address.len() + data.len() + 1) as u8;
+        let checksum = Self::checksum(byte_count, address, data);
+
+        let mut s = String::with_capacity(4 + 2 * (address.len() + data.len() + 2));
+        s.push('S');
+        s.push_str(&type_digit.to_string());
+        s.push_str(&format!("{:02X}", byte_count));
+        for b in address {
+            s.push_str(&format!("{:02X}", b));
+        }
+        for b in data {
+            s.push_str(&format!("{:02X}", b));
+        }
+        s.push_str(&format!("{:02X}", checksum));
+        s
+    }
+
     /// Parse an S-Record string into a Record enum
     pub fn parse_from_str(record: &str) -> Result<Self, Error> {
         let record = record.trim();
@@ -90,7 +144,125 @@ impl Record {
         match rec_type {
             "0" => {
                 // S0: Header, address is 2 bytes, data is rest minus checksum
-                let data = &bytes[2..bytes.len() - 1];
+                let data = &bytes[3..bytes.len() - 1];
+                let text = String::from_utf8_lossy(data).to_string();
+                Ok(Record::S0(text))
+            }
+            "1" => {
+                // S1: 16-bit address + data
+                let address = ((bytes[1] as u16) << 8) | (bytes[2] as u16);
+                let data = bytes[3..bytes.len() - 1].to_vec();
+                Ok(Record::S1(Data { address, data }))
+            }
+            "2" => {
+                // S2: 24-bit address + data
+                let address =
+                    ((bytes[1] as u32) << 16) | ((bytes[2] as u32) << 8) | (bytes[3] as u32);
+                let data = bytes[4..bytes.len() - 1].to_vec();
+                Ok(Record::S2(Data { address, data }))
+            }
+            "3" => {
+                // S3: 32-bit address + data
+                let address = ((bytes[1] as u32) << 24)
+                    | ((bytes[2] as u32) << 16)
+                    | ((bytes[3] as u32) << 8)
+                    | (bytes[4] as u32);
+                let data = bytes[5..bytes.len() - 1].to_vec();
+                Ok(Record::S3(Data { address, data }))
+            }
+            "4" => Ok(Record::S4),
+            "5" => {
+                // S5: 16-bit count
+                let count = ((bytes[1] as u16) << 8) | (bytes[2] as u16);
+                Ok(Record::S5(count))
+            }
+            "6" => {
+                // S6: 24-bit count
+                let count =
+                    ((bytes[1] as u32) << 16) | ((bytes[2] as u32) << 8) | (bytes[3] as u32);
+                Ok(Record::S6(count))
+            }
+            "7" => {
+                // S7: 32-bit start address
+                let address = ((bytes[1] as u32) << 24)
+                    | ((bytes[2] as u32) << 16)
+                    | ((bytes[3] as u32) << 8)
+                    | (bytes[4] as u32);
+                Ok(Record::S7(address))
+            }
+            "8" => {
+                // S8: 24-bit start address
+                let address =
+                    ((bytes[1] as u32) << 16) | ((bytes[2] as u32) << 8) | (bytes[3] as u32);
+                Ok(Record::S8(address))
+            }
+            "9" => {
+                // S9: 16-bit start address
+                let address = ((bytes[1] as u16) << 8) | (bytes[2] as u16);
+                Ok(Record::S9(address))
+            }
+            _ => Err(Error::DataLengthError),
+        }
+    }
+
+    /// Parse an S-Record string into a Record enum, the same as [`Record::parse_from_str`]
+    /// but without silently coercing bad input into zero bytes.
+    ///
+    /// Unlike the lenient parser, this:
+    /// - rejects any non-hex nibble with [`Error::UnexpectedCharacter`] instead of
+    ///   substituting `0x00`,
+    /// - checks the declared byte-count field against the number of byte-pairs that
+    ///   actually follow it, returning [`Error::DataLengthError`] on mismatch,
+    /// - returns [`Error::UnexpectedEof`] for a line that is too short to contain the
+    ///   fields its record type requires, rather than panicking on an out-of-bounds index.
+    pub fn parse_from_str_strict(record: &str) -> Result<Self, Error> {
+        let record = record.trim();
+        if !record.is_ascii() {
+            return Err(Error::UnexpectedCharacter);
+        }
+        if !record.starts_with('S') || record.len() < 4 || !record.len().is_multiple_of(2) {
+            return Err(Error::UnexpectedEof);
+        }
+
+        let rec_type = &record[1..2];
+        let mut bytes = Vec::with_capacity((record.len() - 2) / 2);
+        for i in (2..record.len()).step_by(2) {
+            let pair = &record[i..i + 2];
+            if !pair.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(Error::UnexpectedCharacter);
+            }
+            let byte = u8::from_str_radix(pair, 16).map_err(|_| Error::UnexpectedCharacter)?;
+            bytes.push(byte);
+        }
+
+        let min_len = match rec_type {
+            "0" | "1" | "5" | "9" => 4,
+            "2" | "6" | "8" => 5,
+            "3" | "7" => 6,
+            "4" => 2,
+            _ => return Err(Error::DataLengthError),
+        };
+        if bytes.len() < min_len {
+            return Err(Error::UnexpectedEof);
+        }
+
+        // The byte-count field must equal the number of bytes that follow it
+        // (address + data + checksum).
+        let declared_count = bytes[0] as usize;
+        if declared_count != bytes.len() - 1 {
+            return Err(Error::DataLengthError);
+        }
+
+        // Checksum calculation (last byte is checksum)
+        let sum: u32 = bytes.iter().map(|&b| b as u32).sum();
+        if (sum & 0xFF) != 0xFF {
+            return Err(Error::CheckSumError);
+        }
+
+        match rec_type {
+            "0" => {
+                // S0: Header, address is 2 bytes, data is rest minus checksum
+                let data = &bytes[3..bytes.len() - 1];
                 let text = String::from_utf8_lossy(data).to_string();
                 Ok(Record::S0(text))
             }
@@ -152,6 +324,28 @@ impl Record {
     }
 }
 
+impl fmt::Display for Record {
+    /// Render this record as its S-record text line (without a trailing newline).
+    ///
+    /// This is the inverse of [`Record::parse_from_str`]: re-encoding a parsed record
+    /// and re-parsing it yields an equal record.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Record::S0(header) => Self::format(0, &0u16.to_be_bytes(), header.as_bytes()),
+            Record::S1(d) => Self::format(1, &d.address.to_be_bytes(), &d.data),
+            Record::S2(d) => Self::format(2, &d.address.to_be_bytes()[1..], &d.data),
+            Record::S3(d) => Self::format(3, &d.address.to_be_bytes(), &d.data),
+            Record::S4 => Self::format(4, &[], &[]),
+            Record::S5(count) => Self::format(5, &count.to_be_bytes(), &[]),
+            Record::S6(count) => Self::format(6, &count.to_be_bytes()[1..], &[]),
+            Record::S7(address) => Self::format(7, &address.to_be_bytes(), &[]),
+            Record::S8(address) => Self::format(8, &address.to_be_bytes()[1..], &[]),
+            Record::S9(address) => Self::format(9, &address.to_be_bytes(), &[]),
+        };
+        f.write_str(&text)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -221,4 +415,68 @@ mod tests {
             _ => panic!("Expected S1 record"),
         }
     }
+
+    #[test]
+    fn test_to_string_round_trips_s1_record() {
+        let srec = "S1137AF000A0A0D000000000000000000000000072";
+        let rec = Record::parse_from_str(srec).unwrap();
+        assert_eq!(rec.to_string(), srec);
+    }
+
+    #[test]
+    fn test_to_string_round_trips_s2_record() {
+        let srec = "S214010480C04671B604207146014218D0EFF30983C5";
+        let rec = Record::parse_from_str(srec).unwrap();
+        assert_eq!(rec.to_string(), srec);
+    }
+
+    #[test]
+    fn test_parse_strict_rejects_bad_hex_digit() {
+        // Same S1 record as above with the second address nibble replaced by 'G'.
+        let srec = "S1137GF000A0A0D000000000000000000000000072";
+        assert!(matches!(
+            Record::parse_from_str_strict(srec),
+            Err(Error::UnexpectedCharacter)
+        ));
+    }
+
+    #[test]
+    fn test_parse_strict_rejects_byte_count_mismatch() {
+        // Byte count field (0x14, was 0x13) no longer matches the actual remaining bytes.
+        let srec = "S1147AF000A0A0D000000000000000000000000072";
+        assert!(matches!(
+            Record::parse_from_str_strict(srec),
+            Err(Error::DataLengthError)
+        ));
+    }
+
+    #[test]
+    fn test_parse_strict_rejects_truncated_line() {
+        assert!(matches!(
+            Record::parse_from_str_strict("S1"),
+            Err(Error::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn test_parse_strict_accepts_valid_record() {
+        let srec = "S1137AF000A0A0D000000000000000000000000072";
+        assert!(Record::parse_from_str_strict(srec).is_ok());
+    }
+
+    #[test]
+    fn test_parse_strict_s0_header_is_not_shifted() {
+        let srec = "S00600004844521B";
+        match Record::parse_from_str_strict(srec).unwrap() {
+            Record::S0(header) => assert_eq!(header, "HDR"),
+            _ => panic!("Expected S0 record"),
+        }
+    }
+
+    #[test]
+    fn test_to_string_s0_header() {
+        let rec = Record::S0("HDR".to_string());
+        let text = rec.to_string();
+        assert_eq!(Record::parse_from_str(&text).unwrap().to_string(), text);
+    }
 }
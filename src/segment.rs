@@ -0,0 +1,17 @@
+use crate::Address;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A single contiguous block of data at an absolute base address.
+///
+/// Unlike the merged `data`/`data_memory_layout` view, a list of segments keeps
+/// non-contiguous regions (e.g. a vector table at `0x0` and an application at
+/// `0x8000`) distinct instead of concatenating them into one buffer.
+#[derive(Debug, Clone)]
+pub struct Segment {
+    /// Absolute start address of this segment.
+    pub base: Address,
+    /// The segment's raw bytes.
+    pub contents: Vec<u8>,
+}